@@ -0,0 +1,229 @@
+use crate::error::CFFMError;
+use ethers::{
+    abi::{ParamType, Token},
+    providers::{JsonRpcClient, Middleware, Provider},
+    types::{transaction::eth::TypedTransaction, Bytes, TransactionRequest, H160, I256, U256},
+    utils::keccak256,
+};
+use std::sync::Arc;
+
+//A synced Uniswap-V2-style pair.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UniswapV2Pool {
+    pub address: H160,
+    pub token_a: H160,
+    pub token_a_decimals: u8,
+    pub token_b: H160,
+    pub token_b_decimals: u8,
+    pub reserve_0: u128,
+    pub reserve_1: u128,
+    pub fee: u32,
+}
+
+//A synced Uniswap-V3-style pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UniswapV3Pool {
+    pub address: H160,
+    pub token_a: H160,
+    pub token_a_decimals: u8,
+    pub token_b: H160,
+    pub token_b_decimals: u8,
+    pub liquidity: u128,
+    pub sqrt_price: U256,
+    pub tick: i32,
+    pub fee: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pool {
+    UniswapV2(UniswapV2Pool),
+    UniswapV3(UniswapV3Pool),
+}
+
+impl Pool {
+    //The pool's on-chain address.
+    pub fn address(&self) -> H160 {
+        match self {
+            Pool::UniswapV2(pool) => pool.address,
+            Pool::UniswapV3(pool) => pool.address,
+        }
+    }
+
+    //Build the `(target, calldata)` read calls that populate this pool's data. The token addresses are
+    //already known from the `PairCreated`/`PoolCreated` event, so `decimals()` can be read directly
+    //from each token and every target is known up front — which is what makes a single aggregated
+    //multicall possible. The order of the returned calls is the order `populate_data_from_returns`
+    //expects the results in.
+    pub fn get_data_calls(&self) -> Vec<(H160, Bytes)> {
+        match self {
+            Pool::UniswapV2(pool) => vec![
+                (pool.address, selector_calldata("getReserves()")),
+                (pool.token_a, selector_calldata("decimals()")),
+                (pool.token_b, selector_calldata("decimals()")),
+            ],
+            Pool::UniswapV3(pool) => vec![
+                (pool.address, selector_calldata("slot0()")),
+                (pool.address, selector_calldata("liquidity()")),
+                (pool.address, selector_calldata("fee()")),
+                (pool.token_a, selector_calldata("decimals()")),
+                (pool.token_b, selector_calldata("decimals()")),
+            ],
+        }
+    }
+
+    //Decode the return data of `get_data_calls` back into this pool. `returns` must be in the same
+    //order as the calls produced by `get_data_calls`.
+    pub fn populate_data_from_returns<P: JsonRpcClient>(
+        &mut self,
+        returns: &[Bytes],
+    ) -> Result<(), CFFMError<P>> {
+        match self {
+            Pool::UniswapV2(pool) => {
+                expect_len(returns, 3)?;
+
+                let reserves = ethers::abi::decode(
+                    &[
+                        ParamType::Uint(112),
+                        ParamType::Uint(112),
+                        ParamType::Uint(32),
+                    ],
+                    &returns[0],
+                )?;
+                pool.reserve_0 = uint_to_u128(&reserves[0]);
+                pool.reserve_1 = uint_to_u128(&reserves[1]);
+                pool.token_a_decimals = decode_decimals(&returns[1])?;
+                pool.token_b_decimals = decode_decimals(&returns[2])?;
+            }
+            Pool::UniswapV3(pool) => {
+                expect_len(returns, 5)?;
+
+                let slot0 = ethers::abi::decode(
+                    &[
+                        ParamType::Uint(160),
+                        ParamType::Int(24),
+                        ParamType::Uint(16),
+                        ParamType::Uint(16),
+                        ParamType::Uint(16),
+                        ParamType::Uint(8),
+                        ParamType::Bool,
+                    ],
+                    &returns[0],
+                )?;
+                pool.sqrt_price = slot0[0].clone().into_uint().unwrap_or_default();
+                pool.tick = int_to_i32(&slot0[1]);
+
+                let liquidity = ethers::abi::decode(&[ParamType::Uint(128)], &returns[1])?;
+                pool.liquidity = uint_to_u128(&liquidity[0]);
+
+                let fee = ethers::abi::decode(&[ParamType::Uint(24)], &returns[2])?;
+                pool.fee = uint_to_u128(&fee[0]) as u32;
+
+                pool.token_a_decimals = decode_decimals(&returns[3])?;
+                pool.token_b_decimals = decode_decimals(&returns[4])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    //Fetch and populate all of this pool's data via sequential `eth_call`s. This is the per-pool
+    //fallback used when a pool's reads can't be served from an aggregated multicall.
+    pub async fn get_pool_data<P: 'static + JsonRpcClient>(
+        &mut self,
+        provider: Arc<Provider<P>>,
+    ) -> Result<(), CFFMError<P>> {
+        let calls = self.get_data_calls();
+        let mut returns = Vec::with_capacity(calls.len());
+        for (target, calldata) in calls {
+            returns.push(eth_call(&provider, target, calldata).await?);
+        }
+        self.populate_data_from_returns(&returns)
+    }
+
+    //Re-sync only the pool's price/reserve state, leaving the (immutable) token metadata untouched.
+    pub async fn sync_pool<P: 'static + JsonRpcClient>(
+        &mut self,
+        provider: Arc<Provider<P>>,
+    ) -> Result<(), CFFMError<P>> {
+        match self {
+            Pool::UniswapV2(pool) => {
+                let data = eth_call(&provider, pool.address, selector_calldata("getReserves()"))
+                    .await?;
+                let reserves = ethers::abi::decode(
+                    &[
+                        ParamType::Uint(112),
+                        ParamType::Uint(112),
+                        ParamType::Uint(32),
+                    ],
+                    &data,
+                )?;
+                pool.reserve_0 = uint_to_u128(&reserves[0]);
+                pool.reserve_1 = uint_to_u128(&reserves[1]);
+            }
+            Pool::UniswapV3(pool) => {
+                let slot0_data =
+                    eth_call(&provider, pool.address, selector_calldata("slot0()")).await?;
+                let slot0 = ethers::abi::decode(
+                    &[
+                        ParamType::Uint(160),
+                        ParamType::Int(24),
+                        ParamType::Uint(16),
+                        ParamType::Uint(16),
+                        ParamType::Uint(16),
+                        ParamType::Uint(8),
+                        ParamType::Bool,
+                    ],
+                    &slot0_data,
+                )?;
+                pool.sqrt_price = slot0[0].clone().into_uint().unwrap_or_default();
+                pool.tick = int_to_i32(&slot0[1]);
+
+                let liquidity_data =
+                    eth_call(&provider, pool.address, selector_calldata("liquidity()")).await?;
+                let liquidity = ethers::abi::decode(&[ParamType::Uint(128)], &liquidity_data)?;
+                pool.liquidity = uint_to_u128(&liquidity[0]);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+//Encode a zero-argument function call as its 4-byte selector.
+fn selector_calldata(signature: &str) -> Bytes {
+    Bytes::from(keccak256(signature)[..4].to_vec())
+}
+
+//Issue a single `eth_call` against `target` with `calldata`.
+async fn eth_call<P: 'static + JsonRpcClient>(
+    provider: &Arc<Provider<P>>,
+    target: H160,
+    calldata: Bytes,
+) -> Result<Bytes, CFFMError<P>> {
+    let tx = TypedTransaction::Legacy(TransactionRequest::new().to(target).data(calldata));
+    Ok(provider.call(&tx, None).await?)
+}
+
+//Decode an ERC20 `decimals()` return value.
+fn decode_decimals<P: JsonRpcClient>(data: &Bytes) -> Result<u8, CFFMError<P>> {
+    let tokens = ethers::abi::decode(&[ParamType::Uint(8)], data)?;
+    Ok(uint_to_u128(&tokens[0]) as u8)
+}
+
+//Error out when a return slice is shorter than the number of calls that produced it.
+fn expect_len<P: JsonRpcClient>(returns: &[Bytes], expected: usize) -> Result<(), CFFMError<P>> {
+    if returns.len() < expected {
+        Err(CFFMError::EthABIError(ethers::abi::Error::InvalidData))
+    } else {
+        Ok(())
+    }
+}
+
+fn uint_to_u128(token: &Token) -> u128 {
+    token.clone().into_uint().unwrap_or_default().as_u128()
+}
+
+fn int_to_i32(token: &Token) -> i32 {
+    let raw = token.clone().into_int().unwrap_or_default();
+    I256::from_raw(raw).as_i32()
+}
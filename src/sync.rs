@@ -4,16 +4,170 @@ use super::dex::Dex;
 use super::pool::Pool;
 use super::throttle::RequestThrottle;
 use ethers::{
+    abi::{ParamType, Token},
     providers::{JsonRpcClient, Middleware, Provider},
-    types::{BlockNumber, Filter, ValueOrArray, H160, U64},
+    types::{
+        transaction::eth::TypedTransaction, BlockNumber, Bytes, Filter, TransactionRequest,
+        ValueOrArray, H160, U64,
+    },
 };
+use futures::stream::{FuturesUnordered, Stream, StreamExt};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use std::{
+    collections::HashSet,
+    future::Future,
     panic::resume_unwind,
     sync::{Arc, Mutex},
+    time::Duration,
 };
+use tokio::sync::{mpsc, Semaphore};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
+
+//Multicall3 is deployed at this address across most EVM chains.
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+//Default number of pools whose read calls are aggregated into a single multicall.
+pub const DEFAULT_BATCH_SIZE: usize = 100;
+//Default number of blocks scanned per `get_logs` request when searching for pair created events.
+pub const DEFAULT_STEP: u64 = 100000;
+//Default upper bound on the number of simultaneously in-flight block-range/pool tasks.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 100;
+
+//How `get_all_pool_data` reacts to a pool whose read calls fail (e.g. a malformed or self-destructed
+//pool returning bad ABI data).
+#[derive(Debug, Clone, Copy)]
+pub enum ErrorPolicy {
+    //Skip the failing pool but record it, along with the reason, in the returned `SyncReport`.
+    SkipAndCollect,
+    //Abort the whole sync, returning the first error encountered.
+    FailFast,
+    //Attempt the reads up to `attempts` times in total (at least once), with exponential backoff
+    //between attempts, before skipping.
+    Retry { attempts: usize, backoff: Duration },
+}
+
+impl Default for ErrorPolicy {
+    fn default() -> Self {
+        ErrorPolicy::SkipAndCollect
+    }
+}
+
+impl ErrorPolicy {
+    //Number of times a pool's reads are attempted before it is skipped.
+    pub fn max_attempts(&self) -> usize {
+        match self {
+            ErrorPolicy::Retry { attempts, .. } => (*attempts).max(1),
+            _ => 1,
+        }
+    }
+}
+
+//Exponential backoff: `base * 2^attempt`, saturating rather than overflowing for large attempt counts.
+fn backoff_for_attempt(base: Duration, attempt: usize) -> Duration {
+    let factor = 2u32.checked_pow(attempt as u32).unwrap_or(u32::MAX);
+    base.saturating_mul(factor)
+}
+
+//The result of a pool-data sync: the successfully populated pools alongside any pools that were
+//skipped, so a consumer can audit coverage rather than losing pools invisibly.
+#[derive(Debug)]
+pub struct SyncReport<P: JsonRpcClient> {
+    pub pools: Vec<Pool>,
+    pub skipped: Vec<(H160, CFFMError<P>)>,
+}
+
+//Configuration for a bounded, throttled pair sync.
+#[derive(Debug, Clone, Copy)]
+pub struct SyncConfig {
+    //Per-second cap applied by the `RequestThrottle`. A value of 0 disables rate shaping.
+    pub requests_per_second_limit: usize,
+    //Maximum number of block-range/pool tasks allowed to be in-flight at once.
+    pub max_concurrency: usize,
+    //Number of blocks scanned per `get_logs` request.
+    pub step: u64,
+    //Number of pools whose read calls are aggregated into a single multicall. A value of 1 sends one
+    //multicall per pool; 0 is treated as 1.
+    pub batch_size: usize,
+    //How a pool whose read calls fail is handled.
+    pub error_policy: ErrorPolicy,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        SyncConfig {
+            requests_per_second_limit: 0,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            step: DEFAULT_STEP,
+            batch_size: DEFAULT_BATCH_SIZE,
+            error_policy: ErrorPolicy::SkipAndCollect,
+        }
+    }
+}
+
+//A bounded-concurrency executor. It owns a `Semaphore` with `max_concurrency` permits and drives
+//work through a `FuturesUnordered`, so each unit of work acquires a permit before it is spawned and
+//releases it the moment it completes, freeing the slot for the next queued task.
+#[derive(Debug, Clone)]
+pub struct Executor {
+    semaphore: Arc<Semaphore>,
+}
+
+impl Executor {
+    pub fn new(max_concurrency: usize) -> Self {
+        Executor {
+            semaphore: Arc::new(Semaphore::new(max_concurrency.max(1))),
+        }
+    }
+
+    //Drive every future in `tasks` to completion without ever running more than `max_concurrency`
+    //of them simultaneously. A permit is acquired before a task is spawned and dropped when the task
+    //resolves, so a finished task immediately makes room for the next one.
+    pub async fn run<I, F, T, P>(&self, tasks: I) -> Result<Vec<T>, CFFMError<P>>
+    where
+        I: IntoIterator<Item = F>,
+        F: Future<Output = Result<T, CFFMError<P>>> + Send + 'static,
+        T: Send + 'static,
+        P: 'static + JsonRpcClient,
+    {
+        let mut futures = FuturesUnordered::new();
+
+        for task in tasks {
+            let permit = self
+                .semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("Error when acquiring executor semaphore permit");
+
+            futures.push(tokio::spawn(async move {
+                let result = task.await;
+                drop(permit);
+                result
+            }));
+        }
+
+        let mut results = vec![];
+        while let Some(joined) = futures.next().await {
+            match joined {
+                Ok(task_result) => results.push(task_result?),
+                Err(err) => {
+                    if err.is_panic() {
+                        // Resume the panic on the main task
+                        resume_unwind(err.into_panic());
+                    }
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
 
 //Get all pairs and sync reserve values for each Dex in the `dexes` vec.
+//
+//Uses the default error handling of `get_all_pool_data`: a pool whose reads fail with a
+//non-`ProviderError` is silently dropped. Use `sync_pairs_with_config` (which returns a `SyncReport`)
+//when you need a configurable `ErrorPolicy` and diagnostics for skipped pools.
 pub async fn sync_pairs<P: 'static + JsonRpcClient>(
     dexes: Vec<Dex>,
     provider: Arc<Provider<P>>,
@@ -24,6 +178,10 @@ pub async fn sync_pairs<P: 'static + JsonRpcClient>(
 }
 
 //Get all pairs and sync reserve values for each Dex in the `dexes` vec.
+//
+//Uses the default error handling of `get_all_pool_data`: a pool whose reads fail with a
+//non-`ProviderError` is silently dropped. Use `sync_pairs_with_config` when a configurable
+//`ErrorPolicy` and a `SyncReport` of skipped pools are needed.
 pub async fn sync_pairs_with_throttle<P: 'static + JsonRpcClient>(
     dexes: Vec<Dex>,
     provider: Arc<Provider<P>>,
@@ -135,6 +293,497 @@ pub async fn sync_pairs_with_throttle<P: 'static + JsonRpcClient>(
     Ok(aggregated_pools)
 }
 
+//An incremental update produced by a streaming sync: a successfully synced pool, or a pool that was
+//skipped (under a non-`FailFast` policy) along with the reason.
+enum SyncItem<P: JsonRpcClient> {
+    Synced(Pool),
+    Skipped(H160, CFFMError<P>),
+}
+
+//Get all pairs and sync reserve values for each Dex in the `dexes` vec, bounding the number of
+//simultaneously in-flight block-range/pool tasks to `config.max_concurrency` via an `Executor`.
+//
+//Returns a `SyncReport` so the `config.error_policy` diagnostics (pools skipped, and why) reach the
+//caller. Prefer `sync_pairs_stream` directly when a consumer can begin processing pools mid-sync.
+pub async fn sync_pairs_with_config<P: 'static + JsonRpcClient>(
+    dexes: Vec<Dex>,
+    provider: Arc<Provider<P>>,
+    config: SyncConfig,
+) -> Result<SyncReport<P>, CFFMError<P>> {
+    let mut stream = Box::pin(sync_pairs_stream_inner(dexes, provider, config));
+
+    //Aggregate the populated pools and skipped diagnostics streamed from each thread.
+    let mut pools: Vec<Pool> = vec![];
+    let mut skipped: Vec<(H160, CFFMError<P>)> = vec![];
+    while let Some(item) = stream.next().await {
+        match item? {
+            SyncItem::Synced(pool) => pools.push(pool),
+            SyncItem::Skipped(address, err) => skipped.push((address, err)),
+        }
+    }
+
+    Ok(SyncReport { pools, skipped })
+}
+
+//Get all pairs and sync reserve values for each Dex in the `dexes` vec, yielding each `Pool` through a
+//bounded channel as soon as its data is synced rather than buffering the entire universe first. This
+//lets downstream code (arbitrage scanners, indexers) start processing pools mid-sync and caps resident
+//memory to the channel capacity (`config.max_concurrency`).
+//
+//Pools skipped under the `config.error_policy` are not yielded here; use `sync_pairs_with_config` when
+//the skipped-pool diagnostics are needed.
+pub fn sync_pairs_stream<P: 'static + JsonRpcClient>(
+    dexes: Vec<Dex>,
+    provider: Arc<Provider<P>>,
+    config: SyncConfig,
+) -> impl Stream<Item = Result<Pool, CFFMError<P>>> {
+    sync_pairs_stream_inner(dexes, provider, config).filter_map(|item| async move {
+        match item {
+            Ok(SyncItem::Synced(pool)) => Some(Ok(pool)),
+            Ok(SyncItem::Skipped(_, _)) => None,
+            Err(err) => Some(Err(err)),
+        }
+    })
+}
+
+//Core streaming sync shared by `sync_pairs_stream` and `sync_pairs_with_config`, yielding both synced
+//pools and skip diagnostics as `SyncItem`s.
+fn sync_pairs_stream_inner<P: 'static + JsonRpcClient>(
+    dexes: Vec<Dex>,
+    provider: Arc<Provider<P>>,
+    config: SyncConfig,
+) -> impl Stream<Item = Result<SyncItem<P>, CFFMError<P>>> {
+    //Initalize a new request throttle and a bounded executor
+    let request_throttle = Arc::new(Mutex::new(RequestThrottle::new(
+        config.requests_per_second_limit,
+    )));
+    let executor = Executor::new(config.max_concurrency);
+
+    //Bound resident memory to the channel capacity so producers backpressure on a slow consumer.
+    let (tx, rx) =
+        mpsc::channel::<Result<SyncItem<P>, CFFMError<P>>>(config.max_concurrency.max(1));
+
+    //Initialize multi progress bar
+    let multi_progress_bar = MultiProgress::new();
+
+    //For each dex supplied, spawn a worker that streams its pools as soon as they are synced.
+    for dex in dexes {
+        let async_provider = provider.clone();
+        let request_throttle = request_throttle.clone();
+        let executor = executor.clone();
+        let tx = tx.clone();
+        let progress_bar = multi_progress_bar.add(ProgressBar::new(0));
+
+        tokio::spawn(async move {
+            progress_bar.set_style(
+                ProgressStyle::with_template("{msg} {bar:40.cyan/blue} {pos:>7}/{len:7} Blocks")
+                    .expect("Error when setting progress bar style")
+                    .progress_chars("##-"),
+            );
+
+            let current_block = match async_provider.get_block_number().await {
+                Ok(current_block) => current_block,
+                Err(err) => {
+                    let _ = tx.send(Err(err.into())).await;
+                    return;
+                }
+            };
+
+            let pools = match get_all_pools_from_dex_with_config(
+                dex,
+                async_provider.clone(),
+                BlockNumber::Number(current_block),
+                request_throttle.clone(),
+                progress_bar.clone(),
+                config.step,
+                executor.clone(),
+            )
+            .await
+            {
+                Ok(pools) => pools,
+                Err(err) => {
+                    let _ = tx.send(Err(err)).await;
+                    return;
+                }
+            };
+
+            progress_bar.reset();
+            progress_bar.set_style(
+                ProgressStyle::with_template("{msg} {bar:40.cyan/blue} {pos:>7}/{len:7} Pairs")
+                    .expect("Error when setting progress bar style")
+                    .progress_chars("##-"),
+            );
+
+            //Sync pool data through the Multicall3-batched path, honoring the error policy, sending
+            //each pool and skip diagnostic into `tx` as soon as its chunk is decoded rather than
+            //buffering the whole dex's pools before the stream yields anything.
+            if let Err(err) = get_all_pool_data_batched_into(
+                pools,
+                dex.factory_address(),
+                async_provider.clone(),
+                request_throttle.clone(),
+                progress_bar.clone(),
+                config.batch_size,
+                config.error_policy,
+                &tx,
+            )
+            .await
+            {
+                let _ = tx.send(Err(err)).await;
+            }
+        });
+    }
+
+    //Drop the original sender so the stream terminates once every worker has finished.
+    drop(tx);
+
+    ReceiverStream::new(rx)
+}
+
+//Resume a sync from a previously written `pool_sync_checkpoint.json`. For each saved dex, only
+//`PairCreated` logs from `checkpoint.block + 1` to the current block are scanned, newly-discovered
+//pools are merged with the saved set, and reserves are re-synced for the existing pools. An updated
+//checkpoint is written on completion, making periodic refreshes O(new blocks) rather than O(chain
+//history).
+//
+//Uses the default error handling of `get_all_pool_data`: a newly-discovered pool whose reads fail
+//with a non-`ProviderError` is silently dropped rather than reported.
+pub async fn sync_pairs_from_checkpoint<P: 'static + JsonRpcClient>(
+    path: String,
+    provider: Arc<Provider<P>>,
+    requests_per_second_limit: usize,
+) -> Result<Vec<Pool>, CFFMError<P>> {
+    //Load the dexes, previously-synced pools and the block the checkpoint was taken at.
+    let (dexes, checkpoint_pools, checkpoint_block) =
+        checkpoint::deconstruct_checkpoint(path.clone());
+
+    //Initalize a new request throttle
+    let request_throttle = Arc::new(Mutex::new(RequestThrottle::new(requests_per_second_limit)));
+    let current_block = provider.get_block_number().await?;
+
+    //Aggregate the populated pools from each thread
+    let mut aggregated_pools: Vec<Pool> = vec![];
+    let mut handles = vec![];
+
+    //Initialize multi progress bar
+    let multi_progress_bar = MultiProgress::new();
+
+    //For each dex, scan only the blocks added since the checkpoint for newly-created pools.
+    for dex in dexes.clone() {
+        let async_provider = provider.clone();
+        let request_throttle = request_throttle.clone();
+        let progress_bar = multi_progress_bar.add(ProgressBar::new(0));
+
+        handles.push(tokio::spawn(async move {
+            progress_bar.set_style(
+                ProgressStyle::with_template("{msg} {bar:40.cyan/blue} {pos:>7}/{len:7} Blocks")
+                    .expect("Error when setting progress bar style")
+                    .progress_chars("##-"),
+            );
+
+            let pools = get_all_pools_from_dex_from_block(
+                dex,
+                async_provider.clone(),
+                BlockNumber::Number(U64([checkpoint_block + 1])),
+                BlockNumber::Number(current_block),
+                request_throttle.clone(),
+                progress_bar.clone(),
+            )
+            .await?;
+
+            progress_bar.reset();
+            progress_bar.set_style(
+                ProgressStyle::with_template("{msg} {bar:40.cyan/blue} {pos:>7}/{len:7} Pairs")
+                    .expect("Error when setting progress bar style")
+                    .progress_chars("##-"),
+            );
+
+            let mut pools = get_all_pool_data(
+                pools,
+                dex.factory_address(),
+                async_provider.clone(),
+                request_throttle.clone(),
+                progress_bar.clone(),
+            )
+            .await?;
+
+            progress_bar.reset();
+            progress_bar.set_style(
+                ProgressStyle::with_template("{msg} {bar:40.cyan/blue} {pos:>7}/{len:7} Pairs")
+                    .expect("Error when setting progress bar style")
+                    .progress_chars("##-"),
+            );
+
+            progress_bar.set_length(pools.len() as u64);
+            progress_bar.set_message(format!(
+                "Syncing reserves for pools from: {}",
+                dex.factory_address()
+            ));
+
+            for pool in pools.iter_mut() {
+                let request_throttle = request_throttle.clone();
+                request_throttle
+                    .lock()
+                    .expect("Error when aquiring request throttle mutex lock")
+                    .increment_or_sleep(1);
+
+                pool.sync_pool(async_provider.clone()).await?;
+            }
+
+            Ok::<_, CFFMError<P>>(pools)
+        }));
+    }
+
+    for handle in handles {
+        match handle.await {
+            Ok(sync_result) => aggregated_pools.extend(sync_result?),
+            Err(err) => {
+                {
+                    if err.is_panic() {
+                        // Resume the panic on the main task
+                        resume_unwind(err.into_panic());
+                    }
+                }
+            }
+        }
+    }
+
+    //Re-sync reserves for the pools already recorded in the checkpoint and merge them in.
+    let mut checkpoint_pools = checkpoint_pools;
+    let progress_bar = multi_progress_bar.add(ProgressBar::new(checkpoint_pools.len() as u64));
+    progress_bar.set_style(
+        ProgressStyle::with_template("{msg} {bar:40.cyan/blue} {pos:>7}/{len:7} Pairs")
+            .expect("Error when setting progress bar style")
+            .progress_chars("##-"),
+    );
+    progress_bar.set_message("Re-syncing reserves for checkpoint pools".to_string());
+
+    for pool in checkpoint_pools.iter_mut() {
+        request_throttle
+            .lock()
+            .expect("Error when aquiring request throttle mutex lock")
+            .increment_or_sleep(1);
+
+        pool.sync_pool(provider.clone()).await?;
+        progress_bar.inc(1);
+    }
+    aggregated_pools.extend(checkpoint_pools);
+
+    //Write an updated checkpoint so the next refresh resumes from here.
+    let latest_block = provider.get_block_number().await?;
+    checkpoint::construct_checkpoint(dexes, &aggregated_pools, latest_block.as_u64(), path);
+
+    //Return the populated aggregated pools vec
+    Ok(aggregated_pools)
+}
+
+//An update from a shutdown-aware worker: a synced pool (tagged with its dex factory so the checkpoint
+//can be scoped per dex), or a signal that a dex finished scanning every block up to `current_block`.
+enum ShutdownUpdate<P: JsonRpcClient> {
+    Synced(H160, Pool),
+    Completed(Dex),
+    Error(CFFMError<P>),
+}
+
+//Get all pairs and sync reserve values for each Dex in the `dexes` vec, watching a `shutdown` signal
+//so a long run can be interrupted cleanly. When the token fires, the pools already synced are drained
+//and a partial checkpoint is written so a later `sync_pairs_from_checkpoint` can resume. The function
+//returns the pools gathered so far instead of panicking or hanging.
+//
+//Each dex worker checks the token before its block scan, races its pool-data sync against it, and
+//checks it again before each reserve sync, taking no further throttle permits once it has fired. A
+//block scan already under way is not cancelled: its spawned `get_logs` tasks still run and take their
+//throttle permits.
+//
+//The checkpoint only records dexes that actually finished scanning to `current_block`, together with
+//their pools. A dex whose task was cancelled before completing is omitted entirely rather than being
+//falsely stamped as fully scanned, so a resume never silently skips a dex's history.
+//
+//Uses the default error handling of `get_all_pool_data`: a pool whose reads fail with a
+//non-`ProviderError` is silently dropped rather than reported.
+pub async fn sync_pairs_with_shutdown<P: 'static + JsonRpcClient>(
+    dexes: Vec<Dex>,
+    provider: Arc<Provider<P>>,
+    requests_per_second_limit: usize,
+    shutdown: CancellationToken,
+) -> Result<Vec<Pool>, CFFMError<P>> {
+    //Initalize a new request throttle
+    let request_throttle = Arc::new(Mutex::new(RequestThrottle::new(requests_per_second_limit)));
+    let current_block = provider.get_block_number().await?;
+
+    //Drain already-synced pools through a channel so they are not lost on shutdown.
+    let (tx, mut rx) = mpsc::channel::<ShutdownUpdate<P>>(dexes.len().max(1));
+
+    //Initialize multi progress bar
+    let multi_progress_bar = MultiProgress::new();
+
+    //For each dex supplied, get all pair created events and get reserve values
+    for dex in dexes {
+        let async_provider = provider.clone();
+        let request_throttle = request_throttle.clone();
+        let shutdown = shutdown.clone();
+        let tx = tx.clone();
+        let progress_bar = multi_progress_bar.add(ProgressBar::new(0));
+
+        tokio::spawn(async move {
+            progress_bar.set_style(
+                ProgressStyle::with_template("{msg} {bar:40.cyan/blue} {pos:>7}/{len:7} Blocks")
+                    .expect("Error when setting progress bar style")
+                    .progress_chars("##-"),
+            );
+
+            if shutdown.is_cancelled() {
+                return;
+            }
+
+            let pools = match get_all_pools_from_dex(
+                dex,
+                async_provider.clone(),
+                BlockNumber::Number(current_block),
+                request_throttle.clone(),
+                progress_bar.clone(),
+            )
+            .await
+            {
+                Ok(pools) => pools,
+                Err(err) => {
+                    let _ = tx.send(ShutdownUpdate::Error(err)).await;
+                    return;
+                }
+            };
+
+            progress_bar.reset();
+            progress_bar.set_style(
+                ProgressStyle::with_template("{msg} {bar:40.cyan/blue} {pos:>7}/{len:7} Pairs")
+                    .expect("Error when setting progress bar style")
+                    .progress_chars("##-"),
+            );
+
+            //Race the bulk reserve sync against the shutdown signal so a cancellation mid-call
+            //returns promptly instead of waiting for every throttle permit it still needs to acquire.
+            let mut pools = tokio::select! {
+                result = get_all_pool_data(
+                    pools,
+                    dex.factory_address(),
+                    async_provider.clone(),
+                    request_throttle.clone(),
+                    progress_bar.clone(),
+                ) => match result {
+                    Ok(pools) => pools,
+                    Err(err) => {
+                        let _ = tx.send(ShutdownUpdate::Error(err)).await;
+                        return;
+                    }
+                },
+                _ = shutdown.cancelled() => return,
+            };
+
+            progress_bar.reset();
+            progress_bar.set_style(
+                ProgressStyle::with_template("{msg} {bar:40.cyan/blue} {pos:>7}/{len:7} Pairs")
+                    .expect("Error when setting progress bar style")
+                    .progress_chars("##-"),
+            );
+
+            progress_bar.set_length(pools.len() as u64);
+            progress_bar.set_message(format!(
+                "Syncing reserves for pools from: {}",
+                dex.factory_address()
+            ));
+
+            let factory_address = dex.factory_address();
+            for pool in pools.iter_mut() {
+                //Stop acquiring new throttle permits once a shutdown has been requested. The dex is
+                //left un-`Completed`, so it is excluded from the checkpoint below.
+                if shutdown.is_cancelled() {
+                    return;
+                }
+
+                let request_throttle = request_throttle.clone();
+                request_throttle
+                    .lock()
+                    .expect("Error when aquiring request throttle mutex lock")
+                    .increment_or_sleep(1);
+
+                if let Err(err) = pool.sync_pool(async_provider.clone()).await {
+                    let _ = tx.send(ShutdownUpdate::Error(err)).await;
+                    return;
+                }
+
+                if tx
+                    .send(ShutdownUpdate::Synced(factory_address, *pool))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+            }
+
+            //Every block up to `current_block` was scanned and every pool synced for this dex.
+            let _ = tx.send(ShutdownUpdate::Completed(dex)).await;
+        });
+    }
+
+    //Drop the original sender so the drain loop terminates once every worker has finished or stopped.
+    drop(tx);
+
+    //Drain synced pools, selecting against the shutdown signal so we return promptly when it fires.
+    //Pools are tagged with their dex factory so the checkpoint can be scoped to completed dexes only.
+    let mut tagged_pools: Vec<(H160, Pool)> = vec![];
+    let mut completed_dexes: Vec<Dex> = vec![];
+    let mut completed_factories: HashSet<H160> = HashSet::new();
+    //Drain via `rx.recv()`/`rx.try_recv()` directly (rather than `ReceiverStream`) so the final
+    //post-cancellation pass below can poll without blocking.
+    let mut cancelled = false;
+    while !cancelled {
+        tokio::select! {
+            maybe_update = rx.recv() => match maybe_update {
+                Some(ShutdownUpdate::Synced(factory, pool)) => tagged_pools.push((factory, pool)),
+                Some(ShutdownUpdate::Completed(dex)) => {
+                    completed_factories.insert(dex.factory_address());
+                    completed_dexes.push(dex);
+                }
+                Some(ShutdownUpdate::Error(err)) => return Err(err),
+                None => break,
+            },
+            _ = shutdown.cancelled() => {
+                //Don't keep waiting on `rx.recv()` for workers still mid-flight; grab whatever is
+                //already queued below and return promptly instead.
+                cancelled = true;
+            }
+        }
+    }
+    if cancelled {
+        while let Ok(update) = rx.try_recv() {
+            match update {
+                ShutdownUpdate::Synced(factory, pool) => tagged_pools.push((factory, pool)),
+                ShutdownUpdate::Completed(dex) => {
+                    completed_factories.insert(dex.factory_address());
+                    completed_dexes.push(dex);
+                }
+                ShutdownUpdate::Error(_) => {}
+            }
+        }
+    }
+
+    //Write a (possibly partial) checkpoint. Only dexes that finished scanning to `current_block` are
+    //recorded, along with their pools, so a resume never claims a dex was scanned further than it was.
+    let checkpoint_pools: Vec<Pool> = tagged_pools
+        .iter()
+        .filter(|(factory, _)| completed_factories.contains(factory))
+        .map(|(_, pool)| *pool)
+        .collect();
+    checkpoint::construct_checkpoint(
+        completed_dexes,
+        &checkpoint_pools,
+        current_block.as_u64(),
+        String::from("pool_sync_checkpoint.json"),
+    );
+
+    //Return the pools gathered so far
+    Ok(tagged_pools.into_iter().map(|(_, pool)| pool).collect())
+}
+
 //Get all pairs
 pub async fn get_all_pools<P: 'static + JsonRpcClient>(
     dexes: Vec<Dex>,
@@ -196,21 +845,44 @@ pub async fn get_all_pools<P: 'static + JsonRpcClient>(
     Ok(aggregated_pools)
 }
 
-//Function to get all pair created events for a given Dex factory address and sync pool data
+//Function to get all pair created events for a given Dex factory address, scanning from the dex's
+//creation block.
 pub async fn get_all_pools_from_dex<P: 'static + JsonRpcClient>(
     dex: Dex,
     provider: Arc<Provider<P>>,
     current_block: BlockNumber,
     request_throttle: Arc<Mutex<RequestThrottle>>,
     progress_bar: ProgressBar,
+) -> Result<Vec<Pool>, CFFMError<P>> {
+    //The creation block was verified within `Dex::new()`, so its conversion to a block number in
+    //`get_all_pools_from_dex_from_block` cannot fail.
+    let from_block = dex.creation_block();
+    get_all_pools_from_dex_from_block(
+        dex,
+        provider,
+        from_block,
+        current_block,
+        request_throttle,
+        progress_bar,
+    )
+    .await
+}
+
+//Function to get all pair created events for a given Dex factory address starting from an explicit
+//`from_block`, so an incremental resync can scan only the blocks added since a checkpoint.
+pub async fn get_all_pools_from_dex_from_block<P: 'static + JsonRpcClient>(
+    dex: Dex,
+    provider: Arc<Provider<P>>,
+    from_block: BlockNumber,
+    current_block: BlockNumber,
+    request_throttle: Arc<Mutex<RequestThrottle>>,
+    progress_bar: ProgressBar,
 ) -> Result<Vec<Pool>, CFFMError<P>> {
     //Define the step for searching a range of blocks for pair created events
     let step = 100000;
-    //Unwrap can be used here because the creation block was verified within `Dex::new()`
-    let from_block = dex
-        .creation_block()
+    let from_block = from_block
         .as_number()
-        .expect("Error using converting creation block as number")
+        .expect("Error using converting from block as number")
         .as_u64();
     let current_block = current_block
         .as_number()
@@ -219,8 +891,9 @@ pub async fn get_all_pools_from_dex<P: 'static + JsonRpcClient>(
 
     let mut aggregated_pairs: Vec<Pool> = vec![];
 
-    //Initialize the progress bar message
-    progress_bar.set_length(current_block - from_block);
+    //Initialize the progress bar message. A checkpoint can be at or ahead of the current head (no new
+    //blocks since the last sync), so saturate rather than underflow the range length.
+    progress_bar.set_length(current_block.saturating_sub(from_block));
     progress_bar.set_message(format!("Getting all pools from: {}", dex.factory_address()));
 
     //Init a new vec to keep track of tasks
@@ -287,6 +960,173 @@ pub async fn get_all_pools_from_dex<P: 'static + JsonRpcClient>(
     Ok(aggregated_pairs)
 }
 
+//Attempt to populate a single pool's data, retrying with exponential backoff per the policy. Each
+//attempt increments the throttle by 4, the same per-pool charge as `get_all_pool_data`, although a V2
+//pool issues 3 reads and a V3 pool 5. Returns `Ok(())` on success, or the
+//last error encountered after exhausting the policy's attempts; the caller decides whether that error
+//aborts the sync (`FailFast`) or is recorded as a skip.
+async fn attempt_get_pool_data<P: 'static + JsonRpcClient>(
+    pool: &mut Pool,
+    provider: Arc<Provider<P>>,
+    request_throttle: Arc<Mutex<RequestThrottle>>,
+    error_policy: ErrorPolicy,
+) -> Result<(), CFFMError<P>> {
+    let attempts = error_policy.max_attempts();
+    let mut last_error = None;
+
+    for attempt in 0..attempts {
+        request_throttle
+            .lock()
+            .expect("Error when aquiring request throttle mutex lock")
+            .increment_or_sleep(4);
+
+        match pool.get_pool_data(provider.clone()).await {
+            Ok(_) => return Ok(()),
+            Err(pair_sync_error) => {
+                //Back off before the next attempt when retrying.
+                if let ErrorPolicy::Retry { backoff, .. } = error_policy {
+                    if attempt + 1 < attempts {
+                        tokio::time::sleep(backoff_for_attempt(backoff, attempt)).await;
+                    }
+                }
+                last_error = Some(pair_sync_error);
+            }
+        }
+    }
+
+    Err(last_error.expect("attempt loop runs at least once"))
+}
+
+//Function to get reserves for each pair in the `pools` vec, applying an `ErrorPolicy` to pools whose
+//reads fail. Unlike `get_all_pool_data`, skipped pools are not silently dropped: they are returned in
+//the `SyncReport` alongside the reason so callers can audit coverage and retry transient failures.
+pub async fn get_all_pool_data_with_policy<P: 'static + JsonRpcClient>(
+    pools: Vec<Pool>,
+    dex_factory_address: H160,
+    provider: Arc<Provider<P>>,
+    request_throttle: Arc<Mutex<RequestThrottle>>,
+    progress_bar: ProgressBar,
+    error_policy: ErrorPolicy,
+) -> Result<SyncReport<P>, CFFMError<P>> {
+    //Create vecs to aggregate the populated pools and the skipped ones.
+    let mut updated_pools: Vec<Pool> = vec![];
+    let mut skipped: Vec<(H160, CFFMError<P>)> = vec![];
+
+    //Initialize the progress bar message
+    progress_bar.set_length(pools.len() as u64);
+    progress_bar.set_message(format!(
+        "Syncing pool data for pairs from: {}",
+        dex_factory_address
+    ));
+
+    //For each pair in the pools vec, get the reserves, honoring the error policy on failure.
+    for mut pool in pools {
+        match attempt_get_pool_data(
+            &mut pool,
+            provider.clone(),
+            request_throttle.clone(),
+            error_policy,
+        )
+        .await
+        {
+            Ok(_) => updated_pools.push(pool),
+            Err(err) => match error_policy {
+                ErrorPolicy::FailFast => return Err(err),
+                _ => skipped.push((pool.address(), err)),
+            },
+        }
+
+        progress_bar.inc(1);
+    }
+
+    Ok(SyncReport {
+        pools: updated_pools,
+        skipped,
+    })
+}
+
+//Function to get all pair created events for a given Dex factory address, bounding the number of
+//simultaneously in-flight block-range tasks through the supplied `Executor`.
+pub async fn get_all_pools_from_dex_with_config<P: 'static + JsonRpcClient>(
+    dex: Dex,
+    provider: Arc<Provider<P>>,
+    current_block: BlockNumber,
+    request_throttle: Arc<Mutex<RequestThrottle>>,
+    progress_bar: ProgressBar,
+    step: u64,
+    executor: Executor,
+) -> Result<Vec<Pool>, CFFMError<P>> {
+    //Unwrap can be used here because the creation block was verified within `Dex::new()`
+    let from_block = dex
+        .creation_block()
+        .as_number()
+        .expect("Error using converting creation block as number")
+        .as_u64();
+    let current_block = current_block
+        .as_number()
+        .expect("Error using converting current block as number")
+        .as_u64();
+
+    //Initialize the progress bar message
+    progress_bar.set_length(current_block - from_block);
+    progress_bar.set_message(format!("Getting all pools from: {}", dex.factory_address()));
+
+    //Init a new vec to keep track of tasks
+    let mut tasks = vec![];
+
+    //For each block within the range, build a task to get all pairs. The executor bounds how many of
+    //these run concurrently.
+    for from_block in (from_block..=current_block).step_by(step as usize) {
+        let request_throttle = request_throttle.clone();
+        let provider = provider.clone();
+        let progress_bar = progress_bar.clone();
+
+        tasks.push(async move {
+            let mut pools = vec![];
+
+            //Get pair created event logs within the block range
+            let to_block = from_block + step;
+
+            //Update the throttle
+            request_throttle
+                .lock()
+                .expect("Error when aquiring request throttle mutex lock")
+                .increment_or_sleep(1);
+
+            let logs = provider
+                .get_logs(
+                    &Filter::new()
+                        .topic0(ValueOrArray::Value(dex.pool_created_event_signature()))
+                        .address(dex.factory_address())
+                        .from_block(BlockNumber::Number(U64([from_block])))
+                        .to_block(BlockNumber::Number(U64([to_block]))),
+                )
+                .await?;
+
+            //For each pair created log, create a new Pair type and add it to the pairs vec
+            for log in logs {
+                let pool = dex.new_empty_pool_from_event(log)?;
+                pools.push(pool);
+            }
+
+            //Increment the progress bar by the step
+            progress_bar.inc(step);
+
+            Ok::<Vec<Pool>, CFFMError<P>>(pools)
+        });
+    }
+
+    //Run the block-range tasks through the bounded executor and flatten the per-range pools.
+    let aggregated_pairs = executor
+        .run::<_, _, Vec<Pool>, P>(tasks)
+        .await?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    Ok(aggregated_pairs)
+}
+
 //Function to get reserves for each pair in the `pairs` vec.
 pub async fn get_all_pool_data<P: 'static + JsonRpcClient>(
     pools: Vec<Pool>,
@@ -333,3 +1173,345 @@ pub async fn get_all_pool_data<P: 'static + JsonRpcClient>(
 
     Ok(updated_pools)
 }
+
+//Slice a flat Multicall3 return array back into per-pool return slices. `call_counts[i]` is the number
+//of calls pool `i` contributed, in order. Each entry is the pool's return data plus a flag that is
+//`true` when any of its calls reverted (or the return array was truncated), signalling the caller to
+//fall back to the per-pool path for that pool.
+fn slice_pool_returns(
+    returns: &[(bool, Bytes)],
+    call_counts: &[usize],
+) -> Vec<(Vec<Bytes>, bool)> {
+    let mut sliced = vec![];
+    let mut offset = 0;
+
+    for &count in call_counts {
+        let end = (offset + count).min(returns.len());
+        let slice = &returns[offset.min(returns.len())..end];
+        offset += count;
+
+        let reverted = slice.len() < count || slice.iter().any(|(success, _)| !success);
+        let data = slice.iter().map(|(_, data)| data.clone()).collect();
+        sliced.push((data, reverted));
+    }
+
+    sliced
+}
+
+//Function to get reserves/token/decimals (and fee/slot0 for V3) for each pair in the `pools` vec,
+//aggregating `batch_size` pools' read calls into a single `eth_call` against the Multicall3 contract
+//rather than issuing ~4 sequential round-trips per pool. Any pool whose aggregated call reverts (or
+//fails to decode) falls back to the per-pool `get_pool_data` path under the given `ErrorPolicy`, so a
+//single bad pool never poisons a whole chunk and skipped pools are reported rather than silently
+//dropped. This turns roughly `4 * pools` requests into `pools / batch_size` requests and the throttle
+//is incremented once per aggregated call.
+//
+//Collects the whole `SyncReport` before returning; prefer `get_all_pool_data_batched_into` directly
+//when a caller (e.g. a streaming sync) can consume each pool as soon as its chunk is decoded.
+pub async fn get_all_pool_data_batched<P: 'static + JsonRpcClient>(
+    pools: Vec<Pool>,
+    dex_factory_address: H160,
+    provider: Arc<Provider<P>>,
+    request_throttle: Arc<Mutex<RequestThrottle>>,
+    progress_bar: ProgressBar,
+    batch_size: usize,
+    error_policy: ErrorPolicy,
+) -> Result<SyncReport<P>, CFFMError<P>> {
+    //Sized to hold every pool's outcome, so `get_all_pool_data_batched_into` never blocks on a send
+    //and this wrapper's "collect the whole report" contract is unaffected.
+    let (tx, mut rx) = mpsc::channel::<Result<SyncItem<P>, CFFMError<P>>>(pools.len().max(1));
+
+    let result = get_all_pool_data_batched_into(
+        pools,
+        dex_factory_address,
+        provider,
+        request_throttle,
+        progress_bar,
+        batch_size,
+        error_policy,
+        &tx,
+    )
+    .await;
+    drop(tx);
+
+    let mut updated_pools: Vec<Pool> = vec![];
+    let mut skipped: Vec<(H160, CFFMError<P>)> = vec![];
+    while let Some(item) = rx.recv().await {
+        match item? {
+            SyncItem::Synced(pool) => updated_pools.push(pool),
+            SyncItem::Skipped(address, err) => skipped.push((address, err)),
+        }
+    }
+    result?;
+
+    Ok(SyncReport {
+        pools: updated_pools,
+        skipped,
+    })
+}
+
+//Core of `get_all_pool_data_batched`: chunks `pools` and aggregates each chunk's read calls into a
+//single multicall, but sends each decoded pool (or skip diagnostic) into `sink` the moment its chunk
+//is resolved instead of buffering them into a `SyncReport`. This lets a caller with its own channel
+//(e.g. `sync_pairs_stream_inner`'s worker) stream pools out as soon as they are synced, capping
+//resident memory to the sink's channel capacity rather than the whole dex's pool count.
+async fn get_all_pool_data_batched_into<P: 'static + JsonRpcClient>(
+    pools: Vec<Pool>,
+    dex_factory_address: H160,
+    provider: Arc<Provider<P>>,
+    request_throttle: Arc<Mutex<RequestThrottle>>,
+    progress_bar: ProgressBar,
+    batch_size: usize,
+    error_policy: ErrorPolicy,
+    sink: &mpsc::Sender<Result<SyncItem<P>, CFFMError<P>>>,
+) -> Result<(), CFFMError<P>> {
+    //Initialize the progress bar message
+    progress_bar.set_length(pools.len() as u64);
+    progress_bar.set_message(format!(
+        "Syncing pool data for pairs from: {}",
+        dex_factory_address
+    ));
+
+    let multicall_address = MULTICALL3_ADDRESS
+        .parse::<H160>()
+        .expect("Error when parsing the Multicall3 address");
+    let batch_size = batch_size.max(1);
+
+    //Chunk the pools and aggregate each chunk's read calls into a single multicall.
+    for mut chunk in pools
+        .chunks(batch_size)
+        .map(<[Pool]>::to_vec)
+        .collect::<Vec<Vec<Pool>>>()
+    {
+        //Collect the (target, calldata) reads for every pool in the chunk, tracking how many calls
+        //belong to each pool so the flat return array can be sliced back per pool.
+        let mut calls: Vec<Token> = vec![];
+        let mut call_counts: Vec<usize> = vec![];
+        for pool in chunk.iter() {
+            let pool_calls = pool.get_data_calls();
+            call_counts.push(pool_calls.len());
+            for (target, calldata) in pool_calls {
+                calls.push(Token::Tuple(vec![
+                    Token::Address(target),
+                    Token::Bool(true),
+                    Token::Bytes(calldata.to_vec()),
+                ]));
+            }
+        }
+
+        //One aggregated call per chunk, so the throttle reflects a single request.
+        request_throttle
+            .lock()
+            .expect("Error when aquiring request throttle mutex lock")
+            .increment_or_sleep(1);
+
+        let returns = multicall3_aggregate3(&provider, multicall_address, calls).await?;
+
+        //Fan-decode the flat return array back into each pool, sending each one into `sink` as soon
+        //as it resolves rather than waiting for the whole dex to finish.
+        for (pool, (return_data, reverted)) in chunk
+            .iter_mut()
+            .zip(slice_pool_returns(&returns, &call_counts))
+        {
+            //Try the aggregated result first; if the call reverted or the decode fails, fall back to
+            //the per-pool path under the error policy.
+            let populated = if reverted {
+                false
+            } else {
+                pool.populate_data_from_returns(&return_data).is_ok()
+            };
+
+            if populated {
+                if sink.send(Ok(SyncItem::Synced(*pool))).await.is_err() {
+                    //The consumer dropped the stream; stop issuing further requests.
+                    return Ok(());
+                }
+            } else {
+                match attempt_get_pool_data(
+                    pool,
+                    provider.clone(),
+                    request_throttle.clone(),
+                    error_policy,
+                )
+                .await
+                {
+                    Ok(_) => {
+                        if sink.send(Ok(SyncItem::Synced(*pool))).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                    Err(err) => match error_policy {
+                        ErrorPolicy::FailFast => return Err(err),
+                        _ => {
+                            if sink
+                                .send(Ok(SyncItem::Skipped(pool.address(), err)))
+                                .await
+                                .is_err()
+                            {
+                                return Ok(());
+                            }
+                        }
+                    },
+                }
+            }
+
+            progress_bar.inc(1);
+        }
+    }
+
+    Ok(())
+}
+
+//Encode and dispatch a single Multicall3 `aggregate3` call, returning the per-call
+//`(success, returnData)` tuples in the same order as `calls`.
+async fn multicall3_aggregate3<P: 'static + JsonRpcClient>(
+    provider: &Arc<Provider<P>>,
+    multicall_address: H160,
+    calls: Vec<Token>,
+) -> Result<Vec<(bool, Bytes)>, CFFMError<P>> {
+    //aggregate3((address,bool,bytes)[]) selector
+    let selector = &ethers::utils::keccak256("aggregate3((address,bool,bytes)[])")[..4];
+    let mut calldata = selector.to_vec();
+    calldata.extend(ethers::abi::encode(&[Token::Array(calls)]));
+
+    let tx = TypedTransaction::Legacy(
+        TransactionRequest::new()
+            .to(multicall_address)
+            .data(calldata),
+    );
+
+    let bytes = provider.call(&tx, None).await?;
+
+    //Decode the returned ((bool,bytes)[]) tuple array.
+    let return_type = ParamType::Array(Box::new(ParamType::Tuple(vec![
+        ParamType::Bool,
+        ParamType::Bytes,
+    ])));
+    let tokens = ethers::abi::decode(&[return_type], &bytes)?;
+
+    let mut results = vec![];
+    if let Some(Token::Array(entries)) = tokens.into_iter().next() {
+        for entry in entries {
+            if let Token::Tuple(fields) = entry {
+                let success = fields[0].clone().into_bool().unwrap_or(false);
+                let data = Bytes::from(fields[1].clone().into_bytes().unwrap_or_default());
+                results.push((success, data));
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::providers::Http;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    //`Executor::run` must drive every task to completion while never letting more than
+    //`max_concurrency` of them run at once.
+    #[tokio::test]
+    async fn executor_run_bounds_concurrency() {
+        let max_concurrency = 4;
+        let executor = Executor::new(max_concurrency);
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let observed_peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks = (0..32).map(|i| {
+            let in_flight = in_flight.clone();
+            let observed_peak = observed_peak.clone();
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                observed_peak.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok::<usize, CFFMError<Http>>(i)
+            }
+        });
+
+        let results = executor.run(tasks).await.expect("executor run failed");
+
+        assert_eq!(results.len(), 32);
+        assert!(
+            observed_peak.load(Ordering::SeqCst) <= max_concurrency,
+            "executor exceeded the configured concurrency cap",
+        );
+    }
+
+    fn ok(byte: u8) -> (bool, Bytes) {
+        (true, Bytes::from(vec![byte]))
+    }
+
+    //Multicall returns are a flat vec of `(success, data)`; `slice_pool_returns` must re-group them
+    //per pool using each pool's call count, in order.
+    #[test]
+    fn slice_pool_returns_groups_by_call_count() {
+        let returns = vec![ok(0), ok(1), ok(2), ok(3), ok(4)];
+        let sliced = slice_pool_returns(&returns, &[3, 2]);
+
+        assert_eq!(sliced.len(), 2);
+        assert_eq!(sliced[0].0, vec![returns[0].1.clone(), returns[1].1.clone(), returns[2].1.clone()]);
+        assert!(!sliced[0].1);
+        assert_eq!(sliced[1].0, vec![returns[3].1.clone(), returns[4].1.clone()]);
+        assert!(!sliced[1].1);
+    }
+
+    //A reverted sub-call anywhere in a pool's slice marks that pool's group as reverted.
+    #[test]
+    fn slice_pool_returns_flags_reverted_call() {
+        let returns = vec![ok(0), (false, Bytes::new()), ok(2)];
+        let sliced = slice_pool_returns(&returns, &[2, 1]);
+
+        assert!(sliced[0].1, "a reverted sub-call should flag the pool's group");
+        assert!(!sliced[1].1);
+    }
+
+    //A returns vec shorter than the declared call counts flags the truncated group.
+    #[test]
+    fn slice_pool_returns_flags_truncated_group() {
+        let returns = vec![ok(0), ok(1)];
+        let sliced = slice_pool_returns(&returns, &[2, 2]);
+
+        assert_eq!(sliced.len(), 2);
+        assert!(!sliced[0].1);
+        assert!(sliced[1].1, "a truncated group should be flagged as reverted");
+        assert!(sliced[1].0.is_empty());
+    }
+
+    //`max_attempts` drives how many times `attempt_get_pool_data` tries a pool: once for the
+    //non-retrying policies, `attempts` (clamped to at least one) for `Retry`.
+    #[test]
+    fn error_policy_max_attempts() {
+        assert_eq!(ErrorPolicy::SkipAndCollect.max_attempts(), 1);
+        assert_eq!(ErrorPolicy::FailFast.max_attempts(), 1);
+        assert_eq!(
+            ErrorPolicy::Retry {
+                attempts: 5,
+                backoff: Duration::from_millis(10),
+            }
+            .max_attempts(),
+            5,
+        );
+        //A zero attempt count is clamped up to one so a retry policy never skips the pool outright.
+        assert_eq!(
+            ErrorPolicy::Retry {
+                attempts: 0,
+                backoff: Duration::from_millis(10),
+            }
+            .max_attempts(),
+            1,
+        );
+    }
+
+    //Backoff grows exponentially with the retry attempt index.
+    #[test]
+    fn backoff_for_attempt_is_exponential() {
+        let base = Duration::from_millis(10);
+        assert_eq!(backoff_for_attempt(base, 0), Duration::from_millis(10));
+        assert_eq!(backoff_for_attempt(base, 1), Duration::from_millis(20));
+        assert_eq!(backoff_for_attempt(base, 2), Duration::from_millis(40));
+        assert_eq!(backoff_for_attempt(base, 3), Duration::from_millis(80));
+    }
+}